@@ -0,0 +1,532 @@
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short, Address,
+    Env, String, Vec,
+};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum JobStatus {
+    Created,
+    Funded,
+    Disputed,
+    Completed,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Milestone {
+    pub description: String,
+    pub amount: i128,
+    pub deadline: u64,
+    pub completed: bool,
+    pub penalty_claimed: i128,
+}
+
+/// Ledger seconds per overdue penalty period.
+const PENALTY_PERIOD: u64 = 100;
+/// Basis points of the milestone amount slashed per overdue period.
+const PENALTY_RATE_BPS: i128 = 500;
+/// Number of overdue periods at which the penalty already reaches 100% of
+/// the milestone amount; periods beyond this add nothing further, so the
+/// accrual calculation is capped here before multiplying to avoid an i128
+/// overflow on large amounts combined with a far-future timestamp.
+const MAX_PENALTY_PERIODS: u64 = (10_000 / PENALTY_RATE_BPS) as u64;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Job {
+    pub client: Address,
+    pub freelancer: Address,
+    pub token: Address,
+    pub milestones: Vec<Milestone>,
+    pub total_amount: i128,
+    pub job_deadline: u64,
+    pub status: JobStatus,
+    pub arbiter: Option<Address>,
+    pub disputed_milestone: Option<u32>,
+    pub disputed_by: Option<Address>,
+    pub beneficiary: Address,
+}
+
+#[contracttype]
+pub enum DataKey {
+    JobCount,
+    Job(u64),
+    ClientJobs(Address),
+    FreelancerJobs(Address),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Error {
+    JobNotFound = 1,
+    NotClient = 2,
+    NotFreelancer = 3,
+    AlreadyFunded = 4,
+    NotFunded = 5,
+    InvalidMilestoneIndex = 6,
+    InvalidDeadline = 7,
+    MilestoneDeadlineExceeded = 8,
+    NotParticipant = 9,
+    NoDispute = 10,
+    NotArbiter = 11,
+    MilestoneAlreadySettled = 12,
+}
+
+#[contract]
+pub struct EscrowContract;
+
+#[contractimpl]
+impl EscrowContract {
+    pub fn create_job(
+        env: Env,
+        client: Address,
+        freelancer: Address,
+        token: Address,
+        milestones: Vec<(String, i128, u64)>,
+        job_deadline: u64,
+        arbiter: Option<Address>,
+    ) -> u64 {
+        client.require_auth();
+
+        let now = env.ledger().timestamp();
+        let mut total_amount: i128 = 0;
+        let mut stored_milestones = Vec::new(&env);
+        for (description, amount, deadline) in milestones.iter() {
+            if deadline <= now {
+                panic_with_error!(&env, Error::InvalidDeadline);
+            }
+            total_amount += amount;
+            stored_milestones.push_back(Milestone {
+                description,
+                amount,
+                deadline,
+                completed: false,
+                penalty_claimed: 0,
+            });
+        }
+
+        let job_id = Self::next_job_id(&env);
+        let beneficiary = freelancer.clone();
+        let job = Job {
+            client,
+            freelancer,
+            token,
+            milestones: stored_milestones,
+            total_amount,
+            job_deadline,
+            status: JobStatus::Created,
+            arbiter,
+            disputed_milestone: None,
+            disputed_by: None,
+            beneficiary,
+        };
+        Self::save_job(&env, job_id, &job);
+        Self::index_job(&env, DataKey::ClientJobs(job.client.clone()), job_id);
+        Self::index_job(&env, DataKey::FreelancerJobs(job.freelancer.clone()), job_id);
+
+        env.events().publish(
+            (symbol_short!("job"), symbol_short!("created"), job_id),
+            (job.client.clone(), job.freelancer.clone(), total_amount, job_deadline),
+        );
+
+        job_id
+    }
+
+    pub fn fund_job(env: Env, job_id: u64, client: Address) -> Result<(), Error> {
+        client.require_auth();
+
+        let mut job = Self::load_job(&env, job_id)?;
+        if job.client != client {
+            return Err(Error::NotClient);
+        }
+        if job.status != JobStatus::Created {
+            return Err(Error::AlreadyFunded);
+        }
+
+        let token_client = soroban_sdk::token::Client::new(&env, &job.token);
+        token_client.transfer(&client, &env.current_contract_address(), &job.total_amount);
+
+        job.status = JobStatus::Funded;
+        Self::save_job(&env, job_id, &job);
+
+        env.events().publish(
+            (symbol_short!("job"), symbol_short!("funded"), job_id),
+            job.total_amount,
+        );
+        Ok(())
+    }
+
+    pub fn submit_milestone(
+        env: Env,
+        job_id: u64,
+        milestone_index: u32,
+        freelancer: Address,
+    ) -> Result<(), Error> {
+        freelancer.require_auth();
+
+        let mut job = Self::load_job(&env, job_id)?;
+        if job.freelancer != freelancer {
+            return Err(Error::NotFreelancer);
+        }
+        if job.status != JobStatus::Funded {
+            return Err(Error::NotFunded);
+        }
+
+        let now = env.ledger().timestamp();
+        let mut milestone = job
+            .milestones
+            .get(milestone_index)
+            .ok_or(Error::InvalidMilestoneIndex)?;
+        if now > milestone.deadline {
+            return Err(Error::MilestoneDeadlineExceeded);
+        }
+
+        let payout = milestone.amount - milestone.penalty_claimed;
+        let token_client = soroban_sdk::token::Client::new(&env, &job.token);
+        token_client.transfer(&env.current_contract_address(), &job.beneficiary, &payout);
+
+        milestone.completed = true;
+        job.milestones.set(milestone_index, milestone);
+        if job.milestones.iter().all(|m| m.completed) {
+            job.status = JobStatus::Completed;
+        }
+        Self::save_job(&env, job_id, &job);
+
+        env.events().publish(
+            (symbol_short!("milestone"), symbol_short!("submitted"), job_id, milestone_index),
+            (job.beneficiary.clone(), payout),
+        );
+        Ok(())
+    }
+
+    /// Redirects future milestone payouts for a job to `new_beneficiary`
+    /// (e.g. a multisig or payment splitter) without cancelling the
+    /// contract. Only the freelancer may change it.
+    pub fn change_beneficiary(
+        env: Env,
+        job_id: u64,
+        freelancer: Address,
+        new_beneficiary: Address,
+    ) -> Result<(), Error> {
+        freelancer.require_auth();
+
+        let mut job = Self::load_job(&env, job_id)?;
+        if job.freelancer != freelancer {
+            return Err(Error::NotFreelancer);
+        }
+
+        job.beneficiary = new_beneficiary;
+        Self::save_job(&env, job_id, &job);
+        Ok(())
+    }
+
+    pub fn get_beneficiary(env: Env, job_id: u64) -> Address {
+        match Self::load_job(&env, job_id) {
+            Ok(job) => job.beneficiary,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    pub fn extend_deadline(
+        env: Env,
+        job_id: u64,
+        milestone_index: u32,
+        new_deadline: u64,
+    ) -> Result<(), Error> {
+        let mut job = Self::load_job(&env, job_id)?;
+        job.client.require_auth();
+
+        let mut milestone = job
+            .milestones
+            .get(milestone_index)
+            .ok_or(Error::InvalidMilestoneIndex)?;
+        if new_deadline <= milestone.deadline {
+            return Err(Error::InvalidDeadline);
+        }
+
+        milestone.deadline = new_deadline;
+        job.milestones.set(milestone_index, milestone);
+        Self::save_job(&env, job_id, &job);
+
+        env.events().publish(
+            (symbol_short!("milestone"), symbol_short!("extended"), job_id, milestone_index),
+            new_deadline,
+        );
+        Ok(())
+    }
+
+    pub fn is_milestone_overdue(env: Env, job_id: u64, milestone_index: u32) -> bool {
+        let job = match Self::load_job(&env, job_id) {
+            Ok(job) => job,
+            Err(e) => panic_with_error!(&env, e),
+        };
+        let milestone = match job.milestones.get(milestone_index) {
+            Some(milestone) => milestone,
+            None => panic_with_error!(&env, Error::InvalidMilestoneIndex),
+        };
+        !milestone.completed && env.ledger().timestamp() > milestone.deadline
+    }
+
+    /// Either the client or the freelancer can flag a not-yet-settled
+    /// milestone as disputed, which halts further payouts until the job's
+    /// arbiter steps in. A milestone that was already submitted (and so paid
+    /// out) cannot be disputed.
+    pub fn open_dispute(
+        env: Env,
+        job_id: u64,
+        milestone_index: u32,
+        caller: Address,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+
+        let mut job = Self::load_job(&env, job_id)?;
+        if caller != job.client && caller != job.freelancer {
+            return Err(Error::NotParticipant);
+        }
+        if job.arbiter.is_none() {
+            return Err(Error::NotArbiter);
+        }
+        if job.status != JobStatus::Funded {
+            return Err(Error::NotFunded);
+        }
+        let milestone = job
+            .milestones
+            .get(milestone_index)
+            .ok_or(Error::InvalidMilestoneIndex)?;
+        if milestone.completed {
+            return Err(Error::MilestoneAlreadySettled);
+        }
+
+        job.status = JobStatus::Disputed;
+        job.disputed_milestone = Some(milestone_index);
+        job.disputed_by = Some(caller);
+        Self::save_job(&env, job_id, &job);
+        Ok(())
+    }
+
+    /// Releases the disputed milestone's funds to the job's current
+    /// beneficiary (the freelancer unless changed). Only the job's designated
+    /// arbiter may call this.
+    pub fn resolve_dispute_release(
+        env: Env,
+        job_id: u64,
+        milestone_index: u32,
+        arbiter: Address,
+    ) -> Result<(), Error> {
+        arbiter.require_auth();
+
+        let mut job = Self::load_job(&env, job_id)?;
+        Self::require_arbiter_for_dispute(&job, &arbiter, milestone_index)?;
+
+        let mut milestone = job
+            .milestones
+            .get(milestone_index)
+            .ok_or(Error::InvalidMilestoneIndex)?;
+
+        let payout = milestone.amount - milestone.penalty_claimed;
+        let token_client = soroban_sdk::token::Client::new(&env, &job.token);
+        token_client.transfer(&env.current_contract_address(), &job.beneficiary, &payout);
+
+        milestone.completed = true;
+        job.milestones.set(milestone_index, milestone);
+        Self::clear_dispute(&mut job);
+        Self::save_job(&env, job_id, &job);
+        Ok(())
+    }
+
+    /// Returns the disputed milestone's funds to the client. Only the job's
+    /// designated arbiter may call this.
+    pub fn resolve_dispute_refund(
+        env: Env,
+        job_id: u64,
+        milestone_index: u32,
+        arbiter: Address,
+    ) -> Result<(), Error> {
+        arbiter.require_auth();
+
+        let mut job = Self::load_job(&env, job_id)?;
+        Self::require_arbiter_for_dispute(&job, &arbiter, milestone_index)?;
+
+        let mut milestone = job
+            .milestones
+            .get(milestone_index)
+            .ok_or(Error::InvalidMilestoneIndex)?;
+
+        let payout = milestone.amount - milestone.penalty_claimed;
+        let token_client = soroban_sdk::token::Client::new(&env, &job.token);
+        token_client.transfer(&env.current_contract_address(), &job.client, &payout);
+
+        milestone.completed = true;
+        job.milestones.set(milestone_index, milestone);
+        Self::clear_dispute(&mut job);
+        Self::save_job(&env, job_id, &job);
+        Ok(())
+    }
+
+    /// Lets the client reclaim a growing fraction of an overdue milestone's
+    /// locked funds. The refundable portion grows by `PENALTY_RATE_BPS` of
+    /// the milestone amount for every `PENALTY_PERIOD` the deadline has been
+    /// missed, capped at the full milestone amount.
+    pub fn claim_overdue_penalty(
+        env: Env,
+        job_id: u64,
+        milestone_index: u32,
+        client: Address,
+    ) -> Result<i128, Error> {
+        client.require_auth();
+
+        let mut job = Self::load_job(&env, job_id)?;
+        if job.client != client {
+            return Err(Error::NotClient);
+        }
+        if job.status != JobStatus::Funded {
+            return Err(Error::NotFunded);
+        }
+
+        let mut milestone = job
+            .milestones
+            .get(milestone_index)
+            .ok_or(Error::InvalidMilestoneIndex)?;
+        if milestone.completed {
+            return Ok(0);
+        }
+
+        let accrued = Self::compute_penalty_accrued(&env, &milestone);
+        let newly_claimable = accrued - milestone.penalty_claimed;
+        if newly_claimable <= 0 {
+            return Ok(0);
+        }
+
+        let token_client = soroban_sdk::token::Client::new(&env, &job.token);
+        token_client.transfer(&env.current_contract_address(), &job.client, &newly_claimable);
+
+        milestone.penalty_claimed = accrued;
+        job.milestones.set(milestone_index, milestone);
+        Self::save_job(&env, job_id, &job);
+
+        env.events().publish(
+            (symbol_short!("milestone"), symbol_short!("penalty"), job_id, milestone_index),
+            newly_claimable,
+        );
+        Ok(newly_claimable)
+    }
+
+    /// Returns the total penalty currently accrued against an overdue
+    /// milestone, regardless of how much of it has already been claimed.
+    pub fn penalty_accrued(env: Env, job_id: u64, milestone_index: u32) -> i128 {
+        let job = match Self::load_job(&env, job_id) {
+            Ok(job) => job,
+            Err(e) => panic_with_error!(&env, e),
+        };
+        let milestone = match job.milestones.get(milestone_index) {
+            Some(milestone) => milestone,
+            None => panic_with_error!(&env, Error::InvalidMilestoneIndex),
+        };
+        Self::compute_penalty_accrued(&env, &milestone)
+    }
+
+    pub fn get_job(env: Env, job_id: u64) -> Job {
+        match Self::load_job(&env, job_id) {
+            Ok(job) => job,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    pub fn get_job_count(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::JobCount)
+            .unwrap_or(0)
+    }
+
+    pub fn get_jobs_by_client(env: Env, client: Address) -> Vec<u64> {
+        env.storage()
+            .instance()
+            .get(&DataKey::ClientJobs(client))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    pub fn get_jobs_by_freelancer(env: Env, freelancer: Address) -> Vec<u64> {
+        env.storage()
+            .instance()
+            .get(&DataKey::FreelancerJobs(freelancer))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    fn require_arbiter_for_dispute(
+        job: &Job,
+        arbiter: &Address,
+        milestone_index: u32,
+    ) -> Result<(), Error> {
+        if job.arbiter.as_ref() != Some(arbiter) {
+            return Err(Error::NotArbiter);
+        }
+        if job.status != JobStatus::Disputed || job.disputed_milestone != Some(milestone_index) {
+            return Err(Error::NoDispute);
+        }
+        let milestone = job
+            .milestones
+            .get(milestone_index)
+            .ok_or(Error::InvalidMilestoneIndex)?;
+        if milestone.completed {
+            return Err(Error::MilestoneAlreadySettled);
+        }
+        Ok(())
+    }
+
+    fn clear_dispute(job: &mut Job) {
+        job.disputed_milestone = None;
+        job.disputed_by = None;
+        job.status = if job.milestones.iter().all(|m| m.completed) {
+            JobStatus::Completed
+        } else {
+            JobStatus::Funded
+        };
+    }
+
+    fn compute_penalty_accrued(env: &Env, milestone: &Milestone) -> i128 {
+        let now = env.ledger().timestamp();
+        if now <= milestone.deadline {
+            return 0;
+        }
+        let overdue_periods = (now - milestone.deadline) / PENALTY_PERIOD;
+        let capped_periods = overdue_periods.min(MAX_PENALTY_PERIODS);
+        let penalty = milestone.amount * (capped_periods as i128) * PENALTY_RATE_BPS / 10_000;
+        penalty.min(milestone.amount)
+    }
+
+    fn index_job(env: &Env, key: DataKey, job_id: u64) {
+        let mut jobs: Vec<u64> = env.storage().instance().get(&key).unwrap_or_else(|| Vec::new(env));
+        jobs.push_back(job_id);
+        env.storage().instance().set(&key, &jobs);
+    }
+
+    fn next_job_id(env: &Env) -> u64 {
+        let count: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::JobCount)
+            .unwrap_or(0);
+        let job_id = count + 1;
+        env.storage().instance().set(&DataKey::JobCount, &job_id);
+        job_id
+    }
+
+    fn load_job(env: &Env, job_id: u64) -> Result<Job, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Job(job_id))
+            .ok_or(Error::JobNotFound)
+    }
+
+    fn save_job(env: &Env, job_id: u64, job: &Job) {
+        env.storage().instance().set(&DataKey::Job(job_id), job);
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -1,7 +1,11 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{testutils::{Address as _, Ledger}, vec, Env, String};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    vec, Env, IntoVal, String,
+};
 
 #[contract]
 pub struct MockToken;
@@ -33,7 +37,7 @@ fn test_create_job() {
         (String::from_str(&env, "Backend integration"), 1500_i128, 4000_u64),
     ];
 
-    let job_id = client.create_job(&user_client, &freelancer, &token, &milestones, &5000_u64);
+    let job_id = client.create_job(&user_client, &freelancer, &token, &milestones, &5000_u64, &None);
     assert_eq!(job_id, 1);
 
     let job = client.get_job(&job_id);
@@ -63,8 +67,8 @@ fn test_job_count_increments() {
         (String::from_str(&env, "Task 1"), 100_i128, 2000_u64),
     ];
 
-    let id1 = client.create_job(&user, &freelancer, &token, &milestones, &2500_u64);
-    let id2 = client.create_job(&user, &freelancer, &token, &milestones, &2500_u64);
+    let id1 = client.create_job(&user, &freelancer, &token, &milestones, &2500_u64, &None);
+    let id2 = client.create_job(&user, &freelancer, &token, &milestones, &2500_u64, &None);
 
     assert_eq!(id1, 1);
     assert_eq!(id2, 2);
@@ -90,7 +94,7 @@ fn test_create_job_invalid_deadline() {
         (String::from_str(&env, "Task 1"), 100_i128, 500_u64), // Invalid, < 1000
     ];
 
-    client.create_job(&user, &freelancer, &token, &milestones, &2000_u64);
+    client.create_job(&user, &freelancer, &token, &milestones, &2000_u64, &None);
 }
 
 #[test]
@@ -112,7 +116,7 @@ fn test_submit_milestone_past_deadline() {
         (String::from_str(&env, "Task 1"), 100_i128, 2000_u64),
     ];
 
-    let job_id = client.create_job(&user, &freelancer, &token, &milestones, &3000_u64);
+    let job_id = client.create_job(&user, &freelancer, &token, &milestones, &3000_u64, &None);
     client.fund_job(&job_id, &user);
 
     // fast forward past deadline
@@ -139,7 +143,7 @@ fn test_is_milestone_overdue() {
         (String::from_str(&env, "Task 1"), 100_i128, 2000_u64),
     ];
 
-    let job_id = client.create_job(&user, &freelancer, &token, &milestones, &3000_u64);
+    let job_id = client.create_job(&user, &freelancer, &token, &milestones, &3000_u64, &None);
     
     // not overdue initially
     assert_eq!(client.is_milestone_overdue(&job_id, &0), false);
@@ -169,10 +173,559 @@ fn test_extend_deadline() {
         (String::from_str(&env, "Task 1"), 100_i128, 2000_u64),
     ];
 
-    let job_id = client.create_job(&user, &freelancer, &token, &milestones, &3000_u64);
+    let job_id = client.create_job(&user, &freelancer, &token, &milestones, &3000_u64, &None);
 
     client.extend_deadline(&job_id, &0, &4000_u64);
 
     let job = client.get_job(&job_id);
     assert_eq!(job.milestones.get(0).unwrap().deadline, 4000);
 }
+
+#[test]
+fn test_dispute_resolved_by_release() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1000);
+
+    let contract_id = env.register_contract(None, EscrowContract);
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let user = Address::generate(&env);
+    let freelancer = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let token = env.register_contract(None, MockToken);
+
+    let milestones = vec![
+        &env,
+        (String::from_str(&env, "Task 1"), 100_i128, 2000_u64),
+    ];
+
+    let job_id = client.create_job(
+        &user,
+        &freelancer,
+        &token,
+        &milestones,
+        &3000_u64,
+        &Some(arbiter.clone()),
+    );
+    client.fund_job(&job_id, &user);
+    client.open_dispute(&job_id, &0, &user);
+
+    let job = client.get_job(&job_id);
+    assert_eq!(job.status, JobStatus::Disputed);
+
+    client.resolve_dispute_release(&job_id, &0, &arbiter);
+
+    let job = client.get_job(&job_id);
+    assert_eq!(job.status, JobStatus::Completed);
+    assert!(job.milestones.get(0).unwrap().completed);
+}
+
+#[test]
+fn test_dispute_resolved_by_refund() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1000);
+
+    let contract_id = env.register_contract(None, EscrowContract);
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let user = Address::generate(&env);
+    let freelancer = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let token = env.register_contract(None, MockToken);
+
+    let milestones = vec![
+        &env,
+        (String::from_str(&env, "Task 1"), 100_i128, 2000_u64),
+    ];
+
+    let job_id = client.create_job(
+        &user,
+        &freelancer,
+        &token,
+        &milestones,
+        &3000_u64,
+        &Some(arbiter.clone()),
+    );
+    client.fund_job(&job_id, &user);
+    client.open_dispute(&job_id, &0, &freelancer);
+
+    client.resolve_dispute_refund(&job_id, &0, &arbiter);
+
+    let job = client.get_job(&job_id);
+    assert_eq!(job.status, JobStatus::Completed);
+    assert!(job.milestones.get(0).unwrap().completed);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #11)")] // NotArbiter
+fn test_resolve_dispute_rejects_non_arbiter() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1000);
+
+    let contract_id = env.register_contract(None, EscrowContract);
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let user = Address::generate(&env);
+    let freelancer = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let impostor = Address::generate(&env);
+    let token = env.register_contract(None, MockToken);
+
+    let milestones = vec![
+        &env,
+        (String::from_str(&env, "Task 1"), 100_i128, 2000_u64),
+    ];
+
+    let job_id = client.create_job(
+        &user,
+        &freelancer,
+        &token,
+        &milestones,
+        &3000_u64,
+        &Some(arbiter),
+    );
+    client.fund_job(&job_id, &user);
+    client.open_dispute(&job_id, &0, &user);
+
+    client.resolve_dispute_release(&job_id, &0, &impostor);
+}
+
+#[test]
+fn test_overdue_penalty_grows_with_time() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1000);
+
+    let contract_id = env.register_contract(None, EscrowContract);
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let user = Address::generate(&env);
+    let freelancer = Address::generate(&env);
+    let token = env.register_contract(None, MockToken);
+
+    let milestones = vec![
+        &env,
+        (String::from_str(&env, "Task 1"), 1000_i128, 2000_u64),
+    ];
+
+    let job_id = client.create_job(&user, &freelancer, &token, &milestones, &3000_u64, &None);
+    client.fund_job(&job_id, &user);
+
+    // not yet overdue
+    assert_eq!(client.penalty_accrued(&job_id, &0), 0);
+
+    // one penalty period overdue (deadline 2000 + PENALTY_PERIOD 100)
+    env.ledger().with_mut(|l| l.timestamp = 2100);
+    assert_eq!(client.penalty_accrued(&job_id, &0), 50);
+
+    // three penalty periods overdue
+    env.ledger().with_mut(|l| l.timestamp = 2300);
+    assert_eq!(client.penalty_accrued(&job_id, &0), 150);
+
+    let claimed = client.claim_overdue_penalty(&job_id, &0, &user);
+    assert_eq!(claimed, 150);
+
+    // claiming again immediately yields nothing new
+    let claimed_again = client.claim_overdue_penalty(&job_id, &0, &user);
+    assert_eq!(claimed_again, 0);
+}
+
+#[test]
+fn test_overdue_penalty_capped_at_milestone_amount() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1000);
+
+    let contract_id = env.register_contract(None, EscrowContract);
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let user = Address::generate(&env);
+    let freelancer = Address::generate(&env);
+    let token = env.register_contract(None, MockToken);
+
+    let milestones = vec![
+        &env,
+        (String::from_str(&env, "Task 1"), 1000_i128, 2000_u64),
+    ];
+
+    let job_id = client.create_job(&user, &freelancer, &token, &milestones, &3000_u64, &None);
+    client.fund_job(&job_id, &user);
+
+    // far past deadline: 30 penalty periods at 5% each would be 150%, capped at 100%
+    env.ledger().with_mut(|l| l.timestamp = 5000);
+    assert_eq!(client.penalty_accrued(&job_id, &0), 1000);
+
+    let claimed = client.claim_overdue_penalty(&job_id, &0, &user);
+    assert_eq!(claimed, 1000);
+}
+
+#[test]
+fn test_create_and_fund_job_emit_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1000);
+
+    let contract_id = env.register_contract(None, EscrowContract);
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let user = Address::generate(&env);
+    let freelancer = Address::generate(&env);
+    let token = env.register_contract(None, MockToken);
+
+    let milestones = vec![
+        &env,
+        (String::from_str(&env, "Task 1"), 100_i128, 2000_u64),
+    ];
+
+    let job_id = client.create_job(&user, &freelancer, &token, &milestones, &3000_u64, &None);
+    client.fund_job(&job_id, &user);
+
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("job"), symbol_short!("created"), job_id).into_val(&env),
+                (user.clone(), freelancer.clone(), 100_i128, 3000_u64).into_val(&env),
+            ),
+            (
+                contract_id.clone(),
+                (symbol_short!("job"), symbol_short!("funded"), job_id).into_val(&env),
+                100_i128.into_val(&env),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_submit_milestone_emits_event() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1000);
+
+    let contract_id = env.register_contract(None, EscrowContract);
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let user = Address::generate(&env);
+    let freelancer = Address::generate(&env);
+    let token = env.register_contract(None, MockToken);
+
+    let milestones = vec![
+        &env,
+        (String::from_str(&env, "Task 1"), 100_i128, 2000_u64),
+    ];
+
+    let job_id = client.create_job(&user, &freelancer, &token, &milestones, &3000_u64, &None);
+    client.fund_job(&job_id, &user);
+    client.submit_milestone(&job_id, &0, &freelancer);
+
+    let event = env.events().all().last().unwrap();
+    assert_eq!(
+        event,
+        (
+            contract_id.clone(),
+            (symbol_short!("milestone"), symbol_short!("submitted"), job_id, 0_u32).into_val(&env),
+            (freelancer.clone(), 100_i128).into_val(&env),
+        )
+    );
+}
+
+#[test]
+fn test_extend_deadline_emits_event() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1000);
+
+    let contract_id = env.register_contract(None, EscrowContract);
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let user = Address::generate(&env);
+    let freelancer = Address::generate(&env);
+    let token = Address::generate(&env);
+
+    let milestones = vec![
+        &env,
+        (String::from_str(&env, "Task 1"), 100_i128, 2000_u64),
+    ];
+
+    let job_id = client.create_job(&user, &freelancer, &token, &milestones, &3000_u64, &None);
+    client.extend_deadline(&job_id, &0, &4000_u64);
+
+    let event = env.events().all().last().unwrap();
+    assert_eq!(
+        event,
+        (
+            contract_id.clone(),
+            (symbol_short!("milestone"), symbol_short!("extended"), job_id, 0_u32).into_val(&env),
+            4000_u64.into_val(&env),
+        )
+    );
+}
+
+#[test]
+fn test_change_beneficiary_redirects_payout() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1000);
+
+    let contract_id = env.register_contract(None, EscrowContract);
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let user = Address::generate(&env);
+    let freelancer = Address::generate(&env);
+    let payout_wallet = Address::generate(&env);
+    let token = env.register_contract(None, MockToken);
+
+    let milestones = vec![
+        &env,
+        (String::from_str(&env, "Task 1"), 100_i128, 2000_u64),
+    ];
+
+    let job_id = client.create_job(&user, &freelancer, &token, &milestones, &3000_u64, &None);
+    assert_eq!(client.get_beneficiary(&job_id), freelancer);
+
+    client.change_beneficiary(&job_id, &freelancer, &payout_wallet);
+    assert_eq!(client.get_beneficiary(&job_id), payout_wallet);
+
+    client.fund_job(&job_id, &user);
+    client.submit_milestone(&job_id, &0, &freelancer);
+
+    let event = env.events().all().last().unwrap();
+    assert_eq!(
+        event,
+        (
+            contract_id.clone(),
+            (symbol_short!("milestone"), symbol_short!("submitted"), job_id, 0_u32).into_val(&env),
+            (payout_wallet.clone(), 100_i128).into_val(&env),
+        )
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #3)")] // NotFreelancer
+fn test_change_beneficiary_rejects_non_freelancer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1000);
+
+    let contract_id = env.register_contract(None, EscrowContract);
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let user = Address::generate(&env);
+    let freelancer = Address::generate(&env);
+    let impostor = Address::generate(&env);
+    let payout_wallet = Address::generate(&env);
+    let token = Address::generate(&env);
+
+    let milestones = vec![
+        &env,
+        (String::from_str(&env, "Task 1"), 100_i128, 2000_u64),
+    ];
+
+    let job_id = client.create_job(&user, &freelancer, &token, &milestones, &3000_u64, &None);
+
+    client.change_beneficiary(&job_id, &impostor, &payout_wallet);
+}
+
+#[test]
+fn test_jobs_indexed_by_participant() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1000);
+
+    let contract_id = env.register_contract(None, EscrowContract);
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let client_a = Address::generate(&env);
+    let client_b = Address::generate(&env);
+    let freelancer_a = Address::generate(&env);
+    let freelancer_b = Address::generate(&env);
+    let token = Address::generate(&env);
+
+    let milestones = vec![
+        &env,
+        (String::from_str(&env, "Task 1"), 100_i128, 2000_u64),
+    ];
+
+    let job1 = client.create_job(&client_a, &freelancer_a, &token, &milestones, &3000_u64, &None);
+    let job2 = client.create_job(&client_a, &freelancer_b, &token, &milestones, &3000_u64, &None);
+    let job3 = client.create_job(&client_b, &freelancer_a, &token, &milestones, &3000_u64, &None);
+
+    assert_eq!(
+        client.get_jobs_by_client(&client_a),
+        vec![&env, job1, job2]
+    );
+    assert_eq!(client.get_jobs_by_client(&client_b), vec![&env, job3]);
+
+    assert_eq!(
+        client.get_jobs_by_freelancer(&freelancer_a),
+        vec![&env, job1, job3]
+    );
+    assert_eq!(
+        client.get_jobs_by_freelancer(&freelancer_b),
+        vec![&env, job2]
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #12)")] // MilestoneAlreadySettled
+fn test_open_dispute_rejects_already_completed_milestone() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1000);
+
+    let contract_id = env.register_contract(None, EscrowContract);
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let user = Address::generate(&env);
+    let freelancer = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let token = env.register_contract(None, MockToken);
+
+    let milestones = vec![
+        &env,
+        (String::from_str(&env, "Task 1"), 100_i128, 2000_u64),
+        (String::from_str(&env, "Task 2"), 200_i128, 2000_u64),
+    ];
+
+    let job_id = client.create_job(
+        &user,
+        &freelancer,
+        &token,
+        &milestones,
+        &3000_u64,
+        &Some(arbiter),
+    );
+    client.fund_job(&job_id, &user);
+    client.submit_milestone(&job_id, &0, &freelancer);
+
+    // milestone 0 is already settled; re-disputing it must be rejected.
+    client.open_dispute(&job_id, &0, &freelancer);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #5)")] // NotFunded
+fn test_open_dispute_rejects_unfunded_job() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1000);
+
+    let contract_id = env.register_contract(None, EscrowContract);
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let user = Address::generate(&env);
+    let freelancer = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let token = Address::generate(&env);
+
+    let milestones = vec![
+        &env,
+        (String::from_str(&env, "Task 1"), 100_i128, 2000_u64),
+    ];
+
+    let job_id = client.create_job(
+        &user,
+        &freelancer,
+        &token,
+        &milestones,
+        &3000_u64,
+        &Some(arbiter),
+    );
+
+    // job was never funded; opening a dispute must be rejected.
+    client.open_dispute(&job_id, &0, &freelancer);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #11)")] // NotArbiter
+fn test_open_dispute_rejects_job_without_arbiter() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1000);
+
+    let contract_id = env.register_contract(None, EscrowContract);
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let user = Address::generate(&env);
+    let freelancer = Address::generate(&env);
+    let token = env.register_contract(None, MockToken);
+
+    let milestones = vec![
+        &env,
+        (String::from_str(&env, "Task 1"), 100_i128, 2000_u64),
+    ];
+
+    let job_id = client.create_job(&user, &freelancer, &token, &milestones, &3000_u64, &None);
+    client.fund_job(&job_id, &user);
+
+    // no arbiter was ever set; a dispute here could never be resolved, so
+    // opening one must be rejected up front instead of locking the funds.
+    client.open_dispute(&job_id, &0, &freelancer);
+}
+#[test]
+fn test_claim_overdue_penalty_emits_event() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1000);
+
+    let contract_id = env.register_contract(None, EscrowContract);
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let user = Address::generate(&env);
+    let freelancer = Address::generate(&env);
+    let token = env.register_contract(None, MockToken);
+
+    let milestones = vec![
+        &env,
+        (String::from_str(&env, "Task 1"), 1000_i128, 2000_u64),
+    ];
+
+    let job_id = client.create_job(&user, &freelancer, &token, &milestones, &3000_u64, &None);
+    client.fund_job(&job_id, &user);
+
+    env.ledger().with_mut(|l| l.timestamp = 2100);
+    let claimed = client.claim_overdue_penalty(&job_id, &0, &user);
+    assert_eq!(claimed, 50);
+
+    let event = env.events().all().last().unwrap();
+    assert_eq!(
+        event,
+        (
+            contract_id.clone(),
+            (symbol_short!("milestone"), symbol_short!("penalty"), job_id, 0_u32).into_val(&env),
+            50_i128.into_val(&env),
+        )
+    );
+}
+
+#[test]
+fn test_overdue_penalty_does_not_overflow_for_large_amount() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1000);
+
+    let contract_id = env.register_contract(None, EscrowContract);
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let user = Address::generate(&env);
+    let freelancer = Address::generate(&env);
+    let token = env.register_contract(None, MockToken);
+
+    let large_amount = 10_000_000_000_000_000_000_i128;
+    let milestones = vec![
+        &env,
+        (String::from_str(&env, "Task 1"), large_amount, 2000_u64),
+    ];
+
+    let job_id = client.create_job(&user, &freelancer, &token, &milestones, &3000_u64, &None);
+    client.fund_job(&job_id, &user);
+
+    // far past deadline with a huge amount: the pre-cap product would
+    // overflow i128 unless overdue_periods is bounded before the multiply.
+    env.ledger().with_mut(|l| l.timestamp = u64::MAX);
+    assert_eq!(client.penalty_accrued(&job_id, &0), large_amount);
+}